@@ -0,0 +1,90 @@
+#![allow(clippy::unused_unit)]
+use polars::prelude::*;
+use pyo3_polars::derive::polars_expr;
+use std::net::{IpAddr, Ipv6Addr};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::dnsresolver::{resolve_forward_batch, resolve_reverse_batch};
+use crate::iptools::is_private_ipv4;
+use crate::utils::DnsKwargs;
+
+/// True for loopback, RFC1918 IPv4, or unique-local/link-local IPv6
+/// addresses -- the ranges we don't want to hand to an external resolver.
+fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ipv4) => is_private_ipv4(*ipv4) || ipv4.is_loopback(),
+        IpAddr::V6(ipv6) => is_private_ipv6(*ipv6),
+    }
+}
+
+fn is_private_ipv6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    ip.is_loopback()
+        || (segments[0] & 0xfe00) == 0xfc00 // fc00::/7 unique local
+        || (segments[0] & 0xffc0) == 0xfe80 // fe80::/10 link local
+}
+
+// Reverse DNS (PTR) lookup: IP address string -> hostname. Resolves the whole
+// Series concurrently (bounded by kwargs.concurrency) rather than one lookup
+// at a time, with a per-lookup timeout; NXDOMAIN/timeout/private addresses
+// (when kwargs.skip_private is set) all yield null rather than erroring the
+// whole frame
+#[polars_expr(output_type=String)]
+fn pl_reverse_dns(inputs: &[Series], kwargs: DnsKwargs) -> PolarsResult<Series> {
+    let ca: &StringChunked = inputs[0].str()?;
+    let timeout = Duration::from_millis(kwargs.timeout_ms);
+
+    let ips: Vec<Option<IpAddr>> = ca
+        .into_iter()
+        .map(|op_s| {
+            let ip = IpAddr::from_str(op_s?).ok()?;
+            if kwargs.skip_private && is_private_ip(&ip) {
+                return None;
+            }
+            Some(ip)
+        })
+        .collect();
+
+    let hostnames = resolve_reverse_batch(ips, timeout, kwargs.concurrency);
+
+    let mut builder = StringChunkedBuilder::new("reverse_dns", ca.len());
+    for hostname in hostnames {
+        match hostname {
+            Some(hostname) => builder.append_value(hostname),
+            None => builder.append_null(),
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
+
+fn forward_dns_output(_: &[Field]) -> PolarsResult<Field> {
+    Ok(Field::new(
+        PlSmallStr::from("forward_dns"),
+        DataType::List(Box::new(DataType::String)),
+    ))
+}
+
+// Forward DNS lookup: hostname -> list of resolved A/AAAA addresses. Same
+// concurrency/timeout handling as pl_reverse_dns; NXDOMAIN/timeout yield null
+#[polars_expr(output_type_func=forward_dns_output)]
+fn pl_forward_dns(inputs: &[Series], kwargs: DnsKwargs) -> PolarsResult<Series> {
+    let ca: &StringChunked = inputs[0].str()?;
+    let timeout = Duration::from_millis(kwargs.timeout_ms);
+
+    let hostnames: Vec<Option<String>> =
+        ca.into_iter().map(|op_s| op_s.map(String::from)).collect();
+
+    let results = resolve_forward_batch(hostnames, timeout, kwargs.concurrency);
+
+    let mut builder = ListStringChunkedBuilder::new(PlSmallStr::from("forward_dns"), ca.len(), 4);
+    for addresses in results {
+        match addresses {
+            Some(addresses) => builder.append_values_iter(addresses.iter().map(String::as_str)),
+            None => builder.append_null(),
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
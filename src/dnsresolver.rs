@@ -0,0 +1,86 @@
+#![allow(clippy::unused_unit)]
+use futures::stream::{self, StreamExt};
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use lazy_static::lazy_static;
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+// A single shared resolver and Tokio runtime, reused across every call to
+// pl_reverse_dns/pl_forward_dns; building either per-batch would dominate
+// resolution latency with setup overhead.
+lazy_static! {
+    static ref RUNTIME: Runtime =
+        Runtime::new().expect("Could not start Tokio runtime for DNS resolution");
+    static ref RESOLVER: TokioAsyncResolver =
+        TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+}
+
+/// Resolve a PTR record for `ip`, returning `None` on NXDOMAIN, timeout, or
+/// any other resolver error rather than failing the whole batch.
+async fn reverse_one(ip: IpAddr, timeout: Duration) -> Option<String> {
+    let lookup = tokio::time::timeout(timeout, RESOLVER.reverse_lookup(ip))
+        .await
+        .ok()?
+        .ok()?;
+
+    lookup
+        .iter()
+        .next()
+        .map(|name| name.to_string().trim_end_matches('.').to_string())
+}
+
+/// Resolve A/AAAA records for `hostname`, returning `None` on NXDOMAIN,
+/// timeout, or any other resolver error.
+async fn forward_one(hostname: String, timeout: Duration) -> Option<Vec<String>> {
+    let lookup = tokio::time::timeout(timeout, RESOLVER.lookup_ip(hostname.as_str()))
+        .await
+        .ok()?
+        .ok()?;
+
+    let addresses: Vec<String> = lookup.iter().map(|ip| ip.to_string()).collect();
+    (!addresses.is_empty()).then_some(addresses)
+}
+
+/// Resolve every `Some` input concurrently, bounded by `concurrency`
+/// in-flight resolutions, preserving input order in the returned Vec.
+pub fn resolve_reverse_batch(
+    ips: Vec<Option<IpAddr>>,
+    timeout: Duration,
+    concurrency: usize,
+) -> Vec<Option<String>> {
+    RUNTIME.block_on(async {
+        stream::iter(ips)
+            .map(|ip| async move {
+                match ip {
+                    Some(ip) => reverse_one(ip, timeout).await,
+                    None => None,
+                }
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+    })
+}
+
+/// Resolve every `Some` hostname concurrently, bounded by `concurrency`
+/// in-flight resolutions, preserving input order in the returned Vec.
+pub fn resolve_forward_batch(
+    hostnames: Vec<Option<String>>,
+    timeout: Duration,
+    concurrency: usize,
+) -> Vec<Option<Vec<String>>> {
+    RUNTIME.block_on(async {
+        stream::iter(hostnames)
+            .map(|hostname| async move {
+                match hostname {
+                    Some(hostname) => forward_one(hostname, timeout).await,
+                    None => None,
+                }
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+    })
+}
@@ -0,0 +1,113 @@
+#![allow(clippy::unused_unit)]
+use polars::prelude::*;
+use pyo3_polars::derive::polars_expr;
+use serde_json::Value;
+use std::net::IpAddr;
+
+use crate::dynamicdb::DynamicMmdbCache;
+use crate::utils::{create_builders, MmdbLookupKwargs};
+
+/// Walk a dotted field path (e.g. "traits.is_anonymous_proxy") against a
+/// decoded MMDB record, returning the leaf value if every segment resolves.
+fn get_path<'v>(record: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.').try_fold(record, |cur, part| cur.get(part))
+}
+
+fn dtype_from_name(name: &str) -> PolarsResult<DataType> {
+    match name {
+        "string" => Ok(DataType::String),
+        "float" => Ok(DataType::Float64),
+        "uint" => Ok(DataType::UInt64),
+        "bool" => Ok(DataType::Boolean),
+        other => Err(PolarsError::ComputeError(
+            format!(
+                "Unsupported pl_mmdb_lookup field dtype '{}': expected string, float, uint, or bool",
+                other
+            )
+            .into(),
+        )),
+    }
+}
+
+fn mmdb_lookup_fields(kwargs: &MmdbLookupKwargs) -> PolarsResult<Vec<(&str, DataType)>> {
+    kwargs
+        .fields
+        .iter()
+        .map(|(path, dtype_name)| dtype_from_name(dtype_name).map(|dtype| (path.as_str(), dtype)))
+        .collect()
+}
+
+fn mmdb_lookup_output(_: &[Field], kwargs: &MmdbLookupKwargs) -> PolarsResult<Field> {
+    let v: Vec<Field> = mmdb_lookup_fields(kwargs)?
+        .into_iter()
+        .map(|(name, dtype)| Field::new(PlSmallStr::from_str(name), dtype))
+        .collect();
+
+    Ok(Field::new(PlSmallStr::EMPTY, DataType::Struct(v)))
+}
+
+// Query an arbitrary MaxMind .mmdb file (GeoIP2-Enterprise, Anonymous-IP, ISP,
+// Domain, Connection-Type, or any other mmdb) for a caller-supplied list of
+// dotted field paths, without needing new Rust for each database
+#[polars_expr(output_type_func_with_kwargs=mmdb_lookup_output)]
+fn pl_mmdb_lookup(inputs: &[Series], kwargs: MmdbLookupKwargs) -> PolarsResult<Series> {
+    if kwargs.reload_mmdb {
+        DynamicMmdbCache::evict(&kwargs.path);
+    }
+
+    let fields = mmdb_lookup_fields(&kwargs)?;
+
+    let ca: &StringChunked = inputs[0].str()?;
+
+    let mut builders = create_builders(&fields, ca.len());
+
+    ca.into_iter().for_each(|op_s| {
+        if let Some(ip_s) = op_s {
+            if let Ok(ip) = ip_s.parse::<IpAddr>() {
+                match DynamicMmdbCache::lookup(&kwargs.path, ip) {
+                    Ok(record) => {
+                        builders.iter_mut().zip(fields.iter()).for_each(
+                            |(builder, (path, dtype))| match get_path(&record, path) {
+                                Some(Value::Bool(v)) => builder.append_value(*v),
+                                // Coerce to the builder's declared dtype rather than
+                                // branching on the JSON number's own runtime kind,
+                                // otherwise e.g. a "float" field backed by an integer
+                                // MMDB value would append a UInt64 into a Float64
+                                // builder and silently come out null.
+                                Some(Value::Number(n)) => match dtype {
+                                    DataType::UInt64 => match n.as_u64() {
+                                        Some(v) => builder.append_value(v),
+                                        None => builder.append_null(),
+                                    },
+                                    DataType::Float64 => match n.as_f64() {
+                                        Some(v) => builder.append_value(v),
+                                        None => builder.append_null(),
+                                    },
+                                    _ => builder.append_null(),
+                                },
+                                Some(Value::String(s)) => builder.append_value(s.as_str()),
+                                _ => builder.append_null(),
+                            },
+                        );
+                    }
+                    Err(_) => builders
+                        .iter_mut()
+                        .for_each(|builder| builder.append_null()),
+                }
+            } else {
+                // invalid ip, so append nulls for everything
+                builders
+                    .iter_mut()
+                    .for_each(|builder| builder.append_null());
+            }
+        } else {
+            // null input, so append nulls for everything
+            builders
+                .iter_mut()
+                .for_each(|builder| builder.append_null());
+        }
+    });
+
+    let series: Vec<Series> = builders.into_iter().map(|b| b.finish()).collect();
+    StructChunked::from_series(PlSmallStr::from("mmdb_lookup"), &series).map(|ca| ca.into_series())
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::unused_unit)]
+use lazy_static::lazy_static;
+use maxminddb::{Mmap, Reader};
+use polars::prelude::PolarsResult;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::maxmind::open_reader;
+
+// Unlike MaxMindDB/SpurDB/EnrichDB, which each hold a small fixed number of
+// well-known readers, pl_mmdb_lookup can be pointed at any .mmdb file, so the
+// cache is keyed by path and grows to however many distinct files callers
+// have queried.
+lazy_static! {
+    static ref MMDB_CACHE: RwLock<HashMap<String, Reader<Mmap>>> = RwLock::new(HashMap::new());
+}
+
+/// Namespace for the global, path-keyed cache of arbitrary MaxMind MMDB
+/// readers used by `pl_mmdb_lookup`.
+pub struct DynamicMmdbCache;
+
+impl DynamicMmdbCache {
+    /// Look up `ip` in the reader for `path` as a dynamic JSON value,
+    /// opening and caching the reader first if this is the first time
+    /// `path` has been queried. An IP that's valid but absent from the
+    /// database (e.g. a private range) resolves to `Value::Null` rather
+    /// than an error.
+    pub fn lookup(path: &str, ip: IpAddr) -> PolarsResult<Value> {
+        {
+            let cache = MMDB_CACHE.read().unwrap();
+            if let Some(reader) = cache.get(path) {
+                return Ok(reader.lookup::<Value>(ip).unwrap_or(Value::Null));
+            }
+        }
+
+        let reader = open_reader(Path::new(path), path)?;
+        let record = reader.lookup::<Value>(ip).unwrap_or(Value::Null);
+        MMDB_CACHE.write().unwrap().insert(path.to_string(), reader);
+        Ok(record)
+    }
+
+    /// Drop the cached reader for `path`, if any, so the next lookup reopens
+    /// the file from disk. Helpful, particularly in an interactive session
+    /// (e.g., Jupyter), when the user has updated the underlying mmdb file.
+    pub fn evict(path: &str) {
+        MMDB_CACHE.write().unwrap().remove(path);
+    }
+}
@@ -0,0 +1,138 @@
+#![allow(clippy::unused_unit)]
+use maxminddb::geoip2;
+use polars::prelude::*;
+use pyo3_polars::derive::polars_expr;
+use std::net::IpAddr;
+
+use crate::enrichdb::{EnrichDB, ANONYMOUS_FIELDS, ISP_FIELDS};
+use crate::utils::{create_builders, EnrichKwargs};
+
+fn anonymous_full_output(_: &[Field]) -> PolarsResult<Field> {
+    let v: Vec<Field> = ANONYMOUS_FIELDS
+        .iter()
+        .map(|(name, data_type)| Field::new(PlSmallStr::from_str(name), data_type.clone()))
+        .collect();
+
+    Ok(Field::new(PlSmallStr::EMPTY, DataType::Struct(v)))
+}
+
+// Build struct of Boolean anonymity signals from MaxMind's GeoIP2-Anonymous-IP database
+#[polars_expr(output_type_func=anonymous_full_output)]
+fn pl_is_anonymous(inputs: &[Series], kwargs: EnrichKwargs) -> PolarsResult<Series> {
+    if kwargs.reload_mmdb {
+        EnrichDB::reload_anonymous_ip(kwargs.anonymous_ip_path.as_deref())?;
+    }
+
+    let mdb = EnrichDB::anonymous_ip(kwargs.anonymous_ip_path.as_deref())?;
+
+    let ca: &StringChunked = inputs[0].str()?;
+
+    let mut builders = create_builders(&ANONYMOUS_FIELDS, ca.len());
+
+    ca.into_iter().for_each(|op_s| {
+        if let Some(ip_s) = op_s {
+            if let Ok(ip) = ip_s.parse::<IpAddr>() {
+                let result = mdb.lookup(ip);
+
+                // add values to the builders
+                // Important: these must be in same order as ANONYMOUS_FIELDS
+                builders[0].append_value(result.is_anonymous);
+                builders[1].append_value(result.is_anonymous_vpn);
+                builders[2].append_value(result.is_hosting_provider);
+                builders[3].append_value(result.is_public_proxy);
+                builders[4].append_value(result.is_residential_proxy);
+                builders[5].append_value(result.is_tor_exit_node);
+            } else {
+                // invalid ip, so append nulls for everything
+                builders
+                    .iter_mut()
+                    .for_each(|builder| builder.append_null());
+            }
+        } else {
+            // null input, so append nulls for everything
+            builders
+                .iter_mut()
+                .for_each(|builder| builder.append_null());
+        }
+    });
+
+    let series: Vec<Series> = builders.into_iter().map(|b| b.finish()).collect();
+    StructChunked::from_series(PlSmallStr::from("is_anonymous"), &series).map(|ca| ca.into_series())
+}
+
+// Get MaxMind's connection type classification (e.g. Cable/DSL, Cellular, Corporate)
+// for Internet routed IP addresses
+#[polars_expr(output_type=String)]
+fn pl_connection_type(inputs: &[Series], kwargs: EnrichKwargs) -> PolarsResult<Series> {
+    if kwargs.reload_mmdb {
+        EnrichDB::reload_connection_type(kwargs.connection_type_path.as_deref())?;
+    }
+
+    let mdb = EnrichDB::connection_type(kwargs.connection_type_path.as_deref())?;
+    let connection_type_reader = mdb.reader();
+
+    let ca: &StringChunked = inputs[0].str()?;
+
+    let out: StringChunked = ca.apply_into_string_amortized(|value: &str, output: &mut String| {
+        if let Ok(ip) = value.parse::<IpAddr>() {
+            if let Ok(record) = connection_type_reader.lookup::<geoip2::ConnectionType>(ip) {
+                if let Some(connection_type) = record.connection_type {
+                    output.push_str(connection_type);
+                }
+            }
+        }
+    });
+
+    Ok(out.into_series())
+}
+
+fn isp_domain_full_output(_: &[Field]) -> PolarsResult<Field> {
+    let v: Vec<Field> = ISP_FIELDS
+        .iter()
+        .map(|(name, data_type)| Field::new(PlSmallStr::from_str(name), data_type.clone()))
+        .collect();
+
+    Ok(Field::new(PlSmallStr::EMPTY, DataType::Struct(v)))
+}
+
+// Build struct containing ISP/organization (GeoIP2-ISP) and domain (GeoIP2-Domain)
+// metadata of input IP addresses
+#[polars_expr(output_type_func=isp_domain_full_output)]
+fn pl_isp_domain(inputs: &[Series], kwargs: EnrichKwargs) -> PolarsResult<Series> {
+    if kwargs.reload_mmdb {
+        EnrichDB::reload_isp_domain(kwargs.isp_path.as_deref(), kwargs.domain_path.as_deref())?;
+    }
+
+    let mdb = EnrichDB::isp_domain(kwargs.isp_path.as_deref(), kwargs.domain_path.as_deref())?;
+
+    let ca: &StringChunked = inputs[0].str()?;
+
+    let mut builders = create_builders(&ISP_FIELDS, ca.len());
+
+    ca.into_iter().for_each(|op_s| {
+        if let Some(ip_s) = op_s {
+            if let Ok(ip) = ip_s.parse::<IpAddr>() {
+                let result = mdb.lookup(ip);
+
+                // add values to the builders
+                // Important: these must be in same order as ISP_FIELDS
+                builders[0].append_value(result.domain);
+                builders[1].append_value(result.isp);
+                builders[2].append_value(result.organization);
+            } else {
+                // invalid ip, so append nulls for everything
+                builders
+                    .iter_mut()
+                    .for_each(|builder| builder.append_null());
+            }
+        } else {
+            // null input, so append nulls for everything
+            builders
+                .iter_mut()
+                .for_each(|builder| builder.append_null());
+        }
+    });
+
+    let series: Vec<Series> = builders.into_iter().map(|b| b.finish()).collect();
+    StructChunked::from_series(PlSmallStr::from("isp_domain"), &series).map(|ca| ca.into_series())
+}
@@ -0,0 +1,241 @@
+#![allow(clippy::unused_unit)]
+use lazy_static::lazy_static;
+use maxminddb::{geoip2, Mmap, Reader};
+use polars::prelude::*;
+use std::net::IpAddr;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use crate::maxmind::{
+    autoreload_enabled, is_stale, open_loaded_reader, resolve_mmdb_path, LoadedReader,
+};
+
+// Boolean signals returned by the GeoIP2-Anonymous-IP database.
+pub const ANONYMOUS_FIELDS: [(&str, DataType); 6] = [
+    ("is_anonymous", DataType::Boolean),
+    ("is_anonymous_vpn", DataType::Boolean),
+    ("is_hosting_provider", DataType::Boolean),
+    ("is_public_proxy", DataType::Boolean),
+    ("is_residential_proxy", DataType::Boolean),
+    ("is_tor_exit_node", DataType::Boolean),
+];
+
+// Combined fields from the GeoIP2-ISP and GeoIP2-Domain databases.
+pub const ISP_FIELDS: [(&str, DataType); 3] = [
+    ("domain", DataType::String),
+    ("isp", DataType::String),
+    ("organization", DataType::String),
+];
+
+#[derive(Default)]
+pub struct AnonymousIpResult {
+    pub is_anonymous: bool,
+    pub is_anonymous_vpn: bool,
+    pub is_hosting_provider: bool,
+    pub is_public_proxy: bool,
+    pub is_residential_proxy: bool,
+    pub is_tor_exit_node: bool,
+}
+
+pub struct IspResult<'a> {
+    pub domain: &'a str,
+    pub isp: &'a str,
+    pub organization: &'a str,
+}
+
+impl<'a> Default for IspResult<'a> {
+    fn default() -> Self {
+        Self {
+            domain: "",
+            isp: "",
+            organization: "",
+        }
+    }
+}
+
+/// A single lazily-loaded MMDB reader slot: the global lock plus the
+/// filename/label used to open it. Each enrichment database gets its own
+/// slot so an expression that only needs one database (e.g.
+/// pl_connection_type) never has to open the other three.
+struct ReaderSlot {
+    reader: RwLock<Option<LoadedReader>>,
+    filename: &'static str,
+    label: &'static str,
+}
+
+impl ReaderSlot {
+    const fn new(filename: &'static str, label: &'static str) -> Self {
+        Self {
+            reader: RwLock::new(None),
+            filename,
+            label,
+        }
+    }
+
+    /// (Re)open this reader and atomically swap it into the global slot
+    /// under its write lock. Helpful, particularly in an interactive session
+    /// (e.g., Jupyter), when the user has updated the MaxMind mmdb file or
+    /// wants to point at a different path without restarting the Python
+    /// process.
+    fn reload(&self, path: Option<&str>) -> PolarsResult<()> {
+        let loaded = open_loaded_reader(resolve_mmdb_path(path, self.filename)?, self.label)?;
+        *self.reader.write().unwrap() = Some(loaded);
+        Ok(())
+    }
+
+    /// Modeling OnceLock's get_or_init, returns a read-locked handle onto
+    /// this slot, initializing it first if necessary. Once initialized, and
+    /// unless `IPTOOLS_MMDB_NO_AUTORELOAD` is set, cheaply stats the file and
+    /// transparently reopens it if it has changed on disk since it was last
+    /// loaded.
+    fn get_or_init(
+        &self,
+        path: Option<&str>,
+    ) -> PolarsResult<RwLockReadGuard<'_, Option<LoadedReader>>> {
+        let needs_init = self.reader.read().unwrap().is_none();
+        if needs_init {
+            self.reload(path)?;
+        } else if autoreload_enabled() && is_stale(self.reader.read().unwrap().as_ref().unwrap()) {
+            self.reload(path)?;
+        }
+
+        Ok(self.reader.read().unwrap())
+    }
+}
+
+// Lazily loaded global connections to MaxMind's supplementary enrichment
+// mmdb database files, mirroring the ASN/City readers in maxmind.rs. Each
+// reader lives behind its own slot/RwLock, opened independently of the
+// other three, so concurrent lookups (read lock) never block one another
+// and an expression that only touches one database never has to open (or
+// even locate on disk) the others.
+lazy_static! {
+    static ref ANONYMOUS_IP_SLOT: ReaderSlot =
+        ReaderSlot::new("GeoIP2-Anonymous-IP.mmdb", "Anonymous-IP");
+    static ref CONNECTION_TYPE_SLOT: ReaderSlot =
+        ReaderSlot::new("GeoIP2-Connection-Type.mmdb", "Connection-Type");
+    static ref ISP_SLOT: ReaderSlot = ReaderSlot::new("GeoIP2-ISP.mmdb", "ISP");
+    static ref DOMAIN_SLOT: ReaderSlot = ReaderSlot::new("GeoIP2-Domain.mmdb", "Domain");
+}
+
+/// Namespace for the global Anonymous-IP, Connection-Type, ISP, and Domain
+/// MaxMind MMDB readers. Each database is reloaded/initialized independently
+/// so an expression that reads only one of them never requires the others.
+pub struct EnrichDB;
+
+impl EnrichDB {
+    pub fn reload_anonymous_ip(anonymous_ip_path: Option<&str>) -> PolarsResult<()> {
+        ANONYMOUS_IP_SLOT.reload(anonymous_ip_path)
+    }
+
+    pub fn anonymous_ip(anonymous_ip_path: Option<&str>) -> PolarsResult<AnonymousIpReader> {
+        Ok(AnonymousIpReader(
+            ANONYMOUS_IP_SLOT.get_or_init(anonymous_ip_path)?,
+        ))
+    }
+
+    pub fn reload_connection_type(connection_type_path: Option<&str>) -> PolarsResult<()> {
+        CONNECTION_TYPE_SLOT.reload(connection_type_path)
+    }
+
+    pub fn connection_type(
+        connection_type_path: Option<&str>,
+    ) -> PolarsResult<ConnectionTypeReader> {
+        Ok(ConnectionTypeReader(
+            CONNECTION_TYPE_SLOT.get_or_init(connection_type_path)?,
+        ))
+    }
+
+    /// pl_isp_domain reads both the ISP and Domain databases for every row,
+    /// so those two slots are reloaded/initialized together.
+    pub fn reload_isp_domain(
+        isp_path: Option<&str>,
+        domain_path: Option<&str>,
+    ) -> PolarsResult<()> {
+        ISP_SLOT.reload(isp_path)?;
+        DOMAIN_SLOT.reload(domain_path)
+    }
+
+    pub fn isp_domain(
+        isp_path: Option<&str>,
+        domain_path: Option<&str>,
+    ) -> PolarsResult<IspDomainReaders> {
+        Ok(IspDomainReaders {
+            isp: ISP_SLOT.get_or_init(isp_path)?,
+            domain: DOMAIN_SLOT.get_or_init(domain_path)?,
+        })
+    }
+}
+
+/// A read-locked handle onto the global Anonymous-IP reader, held for the
+/// duration of a single expression invocation (i.e. one Series, not one row).
+pub struct AnonymousIpReader<'a>(RwLockReadGuard<'a, Option<LoadedReader>>);
+
+impl<'a> AnonymousIpReader<'a> {
+    pub fn lookup(&self, ip: IpAddr) -> AnonymousIpResult {
+        let reader = &self
+            .0
+            .as_ref()
+            .expect("Anonymous-IP reader initialized by get_or_init")
+            .reader;
+
+        let mut result = AnonymousIpResult::default();
+        if let Ok(record) = reader.lookup::<geoip2::AnonymousIp>(ip) {
+            result.is_anonymous = record.is_anonymous.unwrap_or(false);
+            result.is_anonymous_vpn = record.is_anonymous_vpn.unwrap_or(false);
+            result.is_hosting_provider = record.is_hosting_provider.unwrap_or(false);
+            result.is_public_proxy = record.is_public_proxy.unwrap_or(false);
+            result.is_residential_proxy = record.is_residential_proxy.unwrap_or(false);
+            result.is_tor_exit_node = record.is_tor_exit_node.unwrap_or(false);
+        }
+        result
+    }
+}
+
+/// A read-locked handle onto the global Connection-Type reader, held for the
+/// duration of a single expression invocation (i.e. one Series, not one row).
+pub struct ConnectionTypeReader<'a>(RwLockReadGuard<'a, Option<LoadedReader>>);
+
+impl<'a> ConnectionTypeReader<'a> {
+    pub fn reader(&self) -> &Reader<Mmap> {
+        &self
+            .0
+            .as_ref()
+            .expect("Connection-Type reader initialized by get_or_init")
+            .reader
+    }
+}
+
+/// Read-locked handles onto the global ISP and Domain readers, held together
+/// for the duration of a single expression invocation (i.e. one Series, not
+/// one row), since pl_isp_domain reads both for every row.
+pub struct IspDomainReaders<'a> {
+    isp: RwLockReadGuard<'a, Option<LoadedReader>>,
+    domain: RwLockReadGuard<'a, Option<LoadedReader>>,
+}
+
+impl<'a> IspDomainReaders<'a> {
+    pub fn lookup(&self, ip: IpAddr) -> IspResult<'_> {
+        let mut result = IspResult::default();
+
+        let isp_reader = &self
+            .isp
+            .as_ref()
+            .expect("ISP reader initialized by get_or_init")
+            .reader;
+        if let Ok(record) = isp_reader.lookup::<geoip2::Isp>(ip) {
+            result.isp = record.isp.unwrap_or("");
+            result.organization = record.organization.unwrap_or("");
+        }
+
+        let domain_reader = &self
+            .domain
+            .as_ref()
+            .expect("Domain reader initialized by get_or_init")
+            .reader;
+        if let Ok(record) = domain_reader.lookup::<geoip2::Domain>(ip) {
+            result.domain = record.domain.unwrap_or("");
+        }
+
+        result
+    }
+}
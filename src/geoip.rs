@@ -5,7 +5,7 @@ use pyo3_polars::derive::polars_expr;
 use std::fmt::Write;
 use std::net::IpAddr;
 
-use crate::maxmind::{MaxMindDB, MAXMIND_FIELDS};
+use crate::maxmind::{network_for_match, MaxMindDB, MAXMIND_FIELDS};
 use crate::utils::{create_builders, MMDBKwargs};
 
 // borrowing pattern from github.com/abstractqqq/polars_istr
@@ -22,17 +22,10 @@ fn geoip_full_output(_: &[Field]) -> PolarsResult<Field> {
 #[polars_expr(output_type_func=geoip_full_output)]
 fn pl_full_geoip(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
     if kwargs.reload_mmdb {
-        MaxMindDB::reload()?;
+        MaxMindDB::reload(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
     }
 
-    let binding = MaxMindDB::get_or_init()?;
-    let mdb = binding
-            .as_ref()
-            .ok_or_else(|| PolarsError::ComputeError("Error: MaxMindDB is not initialized. Please ensure that the MMDB files are correctly placed and accessible.".into()))?
-            .as_ref()
-            .map_err(|e| {
-                PolarsError::ComputeError(format!("Failed to initialize MaxMindDB: {}", e).into())
-            })?;
+    let mdb = MaxMindDB::get_or_init(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
 
     let ca: &StringChunked = inputs[0].str()?;
 
@@ -41,23 +34,37 @@ fn pl_full_geoip(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series>
     ca.into_iter().for_each(|op_s| {
         if let Some(ip_s) = op_s {
             if let Ok(ip) = ip_s.parse::<IpAddr>() {
-                let geoipresult = mdb.iplookup(ip);
+                let geoipresult = mdb.iplookup(ip, &kwargs.languages);
 
                 // add values to the builders
                 // Important: these must be in same order as MAXMIND_FIELDS
-                // sort alphabetically to ensure
-                builders[0].append_value(geoipresult.asnnum);
-                builders[1].append_value(geoipresult.asnorg);
-                builders[2].append_value(geoipresult.city);
-                builders[3].append_value(geoipresult.continent);
-                builders[4].append_value(geoipresult.subdivision_iso);
-                builders[5].append_value(geoipresult.subdivision);
+                builders[0].append_value(geoipresult.accuracy_radius);
+                builders[1].append_value(geoipresult.asnnum);
+                builders[2].append_value(geoipresult.asnorg);
+                builders[3].append_value(geoipresult.city);
+                builders[4].append_value(geoipresult.continent);
+                builders[5].append_value(geoipresult.country);
                 builders[6].append_value(geoipresult.country_iso);
-                builders[7].append_value(geoipresult.country);
+                builders[7].append_value(geoipresult.is_in_european_union);
                 builders[8].append_value(geoipresult.latitude);
                 builders[9].append_value(geoipresult.longitude);
-                builders[10].append_value(geoipresult.timezone);
+                match &geoipresult.network {
+                    Some(network) => builders[10].append_value(network.as_str()),
+                    None => builders[10].append_null(),
+                }
                 builders[11].append_value(geoipresult.postalcode);
+                builders[12].append_value(geoipresult.registered_country);
+                builders[13].append_value(geoipresult.registered_country_iso);
+                builders[14].append_value(geoipresult.represented_country);
+                builders[15].append_value(geoipresult.represented_country_iso);
+                builders[16].append_value(geoipresult.subdivision);
+                builders[17].append_value(geoipresult.subdivision_iso);
+                match &geoipresult.subdivisions {
+                    Some(subdivisions) => builders[18]
+                        .append_value(AnyValue::List(Series::new(PlSmallStr::EMPTY, subdivisions))),
+                    None => builders[18].append_null(),
+                }
+                builders[19].append_value(geoipresult.timezone);
             } else {
                 // invalid ip, so append nulls for everything
                 builders
@@ -80,18 +87,10 @@ fn pl_full_geoip(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series>
 #[polars_expr(output_type=String)]
 fn pl_get_asn(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
     if kwargs.reload_mmdb {
-        MaxMindDB::reload()?;
+        MaxMindDB::reload(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
     }
 
-    let binding = MaxMindDB::get_or_init()?;
-    let mdb = binding
-        .as_ref()
-        .ok_or_else(|| PolarsError::ComputeError("MaxMindDB is not initialized".into()))?
-        .as_ref()
-        .map_err(|_| {
-            PolarsError::ComputeError("Failed to initialize MaxMindDB in map_err closure".into())
-        })?;
-
+    let mdb = MaxMindDB::get_or_init(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
     let asn_reader = mdb.asn_reader();
 
     let ca: &StringChunked = inputs[0].str()?;
@@ -115,3 +114,41 @@ fn pl_get_asn(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
 
     Ok(out.into_series())
 }
+
+// Get the routed CIDR block (e.g. "8.8.8.0/24") an IP resolved to in the City
+// database, using the reader's prefix-returning lookup. Lets callers group or
+// dedupe rows by network rather than by individual IP
+#[polars_expr(output_type=String)]
+fn pl_get_network(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
+    if kwargs.reload_mmdb {
+        MaxMindDB::reload(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
+    }
+
+    let mdb = MaxMindDB::get_or_init(kwargs.asn_path.as_deref(), kwargs.city_path.as_deref())?;
+    let city_reader = mdb.city_reader();
+
+    let ca: &StringChunked = inputs[0].str()?;
+
+    // Invalid or unmatched IPs must yield null, not an empty string, so they
+    // don't silently collapse into one bucket under a group-by/dedupe.
+    // apply_into_string_amortized can only emit "", so build this column with
+    // an explicit null-capable builder instead, matching pl_full_geoip's
+    // `network` struct field.
+    let mut builder = StringChunkedBuilder::new(ca.name().clone(), ca.len());
+    ca.into_iter().for_each(|op_s| {
+        let network = op_s
+            .and_then(|value| value.parse::<IpAddr>().ok())
+            .and_then(|ip| {
+                city_reader
+                    .lookup_prefix::<geoip2::City>(ip)
+                    .ok()
+                    .and_then(|(_, prefix_len)| network_for_match(ip, prefix_len))
+            });
+        match network {
+            Some(network) => builder.append_value(network.to_string()),
+            None => builder.append_null(),
+        }
+    });
+
+    Ok(builder.finish().into_series())
+}
@@ -3,7 +3,7 @@ use ipnet::{IpNet, Ipv4Net, Ipv6Net};
 use iptrie::{IpPrefix, RTrieSet};
 use polars::prelude::*;
 use pyo3_polars::derive::polars_expr;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
 /// Returns true if this is a valid IPv4 or IPv6 address
@@ -17,6 +17,13 @@ fn pl_is_valid(inputs: &[Series]) -> PolarsResult<Series> {
     Ok(out.into_series())
 }
 
+/// Returns true if this is a private IPv4 address defined in IETF RFC 1918.
+/// Pulled out of `pl_is_private` so other modules (e.g. dns) can reuse the
+/// same check on a single already-parsed address.
+pub(crate) fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    ip.is_private()
+}
+
 /// Returns true if this is a private IPv4 address defined in IETF RFC 1918
 #[polars_expr(output_type=Boolean)]
 fn pl_is_private(inputs: &[Series]) -> PolarsResult<Series> {
@@ -25,7 +32,7 @@ fn pl_is_private(inputs: &[Series]) -> PolarsResult<Series> {
 
     let out: BooleanChunked =
         ca.apply_nonnull_values_generic(DataType::Boolean, |x| match Ipv4Addr::from_str(x) {
-            Ok(ip) => ip.is_private(),
+            Ok(ip) => is_private_ipv4(ip),
             Err(_) => false,
         });
     Ok(out.into_series())
@@ -73,6 +80,60 @@ fn pl_numeric_to_ipv4(inputs: &[Series]) -> PolarsResult<Series> {
     Ok(builder.finish().into_series())
 }
 
+/// Encode an address as the big-endian bytes of its 128-bit representation,
+/// mapping IPv4 into the IPv4-mapped IPv6 space (`::ffff:a.b.c.d`, per RFC
+/// 4291) so both families round-trip losslessly through the same 16-byte
+/// column. Polars has no native u128 type, so Binary is the natural fit.
+#[polars_expr(output_type=Binary)]
+fn pl_ip_to_numeric(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca = inputs[0].str()?;
+    let mut builder = BinaryChunkedBuilder::new("ip_numeric", ca.len());
+
+    for opt_value in ca.into_iter() {
+        match opt_value.map(IpAddr::from_str) {
+            Some(Ok(ip)) => {
+                let ipv6 = match ip {
+                    IpAddr::V4(ipv4) => ipv4.to_ipv6_mapped(),
+                    IpAddr::V6(ipv6) => ipv6,
+                };
+                builder.append_value(ipv6.octets());
+            }
+            Some(Err(_)) => builder.append_null(), // Handle invalid IP strings
+            None => builder.append_null(),         // Handle null input values
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
+
+/// Decode the 16-byte big-endian representation produced by
+/// `pl_ip_to_numeric` back into a string, printing IPv4-mapped addresses as
+/// dotted-quad IPv4 and everything else as IPv6.
+#[polars_expr(output_type=String)]
+fn pl_numeric_to_ip(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca = inputs[0].binary()?;
+    let mut builder = StringChunkedBuilder::new("ip_string", ca.len());
+
+    for opt_value in ca.into_iter() {
+        if let Some(bytes) = opt_value {
+            match <[u8; 16]>::try_from(bytes) {
+                Ok(octets) => {
+                    let ipv6 = Ipv6Addr::from(octets);
+                    match ipv6.to_ipv4_mapped() {
+                        Some(ipv4) => builder.append_value(ipv4.to_string()),
+                        None => builder.append_value(ipv6.to_string()),
+                    }
+                }
+                Err(_) => builder.append_null(), // Handle malformed (non-16-byte) input
+            }
+        } else {
+            builder.append_null(); // Handle null input values
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
+
 /// Check if IP addresses present in a series of CIDR ranges/prefixes
 #[polars_expr(output_type=Boolean)]
 fn pl_is_in(inputs: &[Series]) -> PolarsResult<Series> {
@@ -127,3 +188,158 @@ fn pl_is_in(inputs: &[Series]) -> PolarsResult<Series> {
 
     Ok(builder.finish().into_series())
 }
+
+/// Classify an already-parsed address into its special-use category, covering
+/// both IPv4 and IPv6. `std::net` predicate methods handle most RFC-defined
+/// ranges; a handful of categories (shared/100.64 space, benchmarking,
+/// reserved, IPv6 documentation) aren't exposed by stable std and are
+/// checked manually.
+pub(crate) fn ip_category(ip: IpAddr) -> &'static str {
+    match ip {
+        IpAddr::V4(ipv4) => {
+            // RFC 6598 Carrier-Grade NAT shared address space, 100.64.0.0/10
+            let shared = Ipv4Net::new(Ipv4Addr::new(100, 64, 0, 0), 10).unwrap();
+            // RFC 2544 benchmarking range, 198.18.0.0/15
+            let benchmarking = Ipv4Net::new(Ipv4Addr::new(198, 18, 0, 0), 15).unwrap();
+            // RFC 1112 reserved range, 240.0.0.0/4
+            let reserved = Ipv4Net::new(Ipv4Addr::new(240, 0, 0, 0), 4).unwrap();
+
+            if ipv4.is_private() {
+                "private"
+            } else if ipv4.is_loopback() {
+                "loopback"
+            } else if ipv4.is_link_local() {
+                "link_local"
+            } else if ipv4.is_multicast() {
+                "multicast"
+            } else if ipv4.is_unspecified() {
+                "unspecified"
+            } else if ipv4.is_documentation() {
+                "documentation"
+            } else if shared.contains(&ipv4) {
+                "shared"
+            } else if benchmarking.contains(&ipv4) {
+                "benchmarking"
+            } else if reserved.contains(&ipv4) {
+                "reserved"
+            } else {
+                "global"
+            }
+        }
+        IpAddr::V6(ipv6) => {
+            // RFC 4193 Unique Local Address space, fc00::/7
+            let ula = Ipv6Net::new(Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 0), 7).unwrap();
+            // RFC 3849 documentation range, 2001:db8::/32
+            let documentation =
+                Ipv6Net::new(Ipv6Addr::new(0x2001, 0x0db8, 0, 0, 0, 0, 0, 0), 32).unwrap();
+            // RFC 5180 benchmarking range, 2001:2::/48
+            let benchmarking =
+                Ipv6Net::new(Ipv6Addr::new(0x2001, 0x0002, 0, 0, 0, 0, 0, 0), 48).unwrap();
+
+            if ipv6.is_loopback() {
+                "loopback"
+            } else if ipv6.is_unicast_link_local() {
+                "link_local"
+            } else if ipv6.is_multicast() {
+                "multicast"
+            } else if ipv6.is_unspecified() {
+                "unspecified"
+            } else if ula.contains(&ipv6) {
+                "private"
+            } else if documentation.contains(&ipv6) {
+                "documentation"
+            } else if benchmarking.contains(&ipv6) {
+                "benchmarking"
+            } else {
+                "global"
+            }
+        }
+    }
+}
+
+/// Classify IPv4/IPv6 addresses into special-use categories (private,
+/// loopback, link_local, multicast, unspecified, documentation, shared,
+/// benchmarking, reserved, global), giving a single vectorized column for
+/// filtering routable vs non-routable traffic instead of composing several
+/// boolean helpers.
+#[polars_expr(output_type=String)]
+fn pl_ip_category(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca = inputs[0].str()?;
+    let mut builder = StringChunkedBuilder::new("ip_category", ca.len());
+
+    for opt_value in ca.into_iter() {
+        match opt_value.map(IpAddr::from_str) {
+            Some(Ok(ip)) => builder.append_value(ip_category(ip)),
+            Some(Err(_)) => builder.append_null(), // Handle invalid IP strings
+            None => builder.append_null(),         // Handle null input values
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
+
+// Longest-prefix match: return the actual matching CIDR (not just whether one
+// exists) from a series of CIDR ranges/prefixes, for joining IPs to prefix metadata
+#[polars_expr(output_type=String)]
+fn pl_longest_prefix_match(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca1 = inputs[0].str()?; // ip addresses to lookup
+    let ca2 = inputs[1].str()?; // ip networks/cidrs
+
+    let mut ipv4_rtrie: RTrieSet<Ipv4Net> = RTrieSet::with_capacity(ca2.len());
+    let mut ipv6_rtrie: RTrieSet<Ipv6Net> = RTrieSet::new();
+
+    // Iterate over ca2, parse as IP range, and add it to the appropriate trie
+    for cidr in ca2.into_iter().flatten() {
+        match IpNet::from_str(cidr) {
+            Ok(IpNet::V4(ipv4)) => {
+                _ = ipv4_rtrie.insert(ipv4);
+            }
+            Ok(IpNet::V6(ipv6)) => {
+                _ = ipv6_rtrie.insert(ipv6);
+            }
+            Err(_) => {
+                return Err(PolarsError::ComputeError(
+                    format!("Invalid CIDR range: {}", cidr).into(),
+                ));
+            }
+        }
+    }
+
+    // Compress the radix trie for faster lookups
+    let ipv4_lctrie = ipv4_rtrie.compress();
+    let ipv6_lctrie = ipv6_rtrie.compress();
+
+    // Prepare builder to collect results
+    let mut builder = StringChunkedBuilder::new("longest_prefix_match", ca1.len());
+
+    for opt_value in ca1.into_iter() {
+        if let Some(value) = opt_value {
+            match IpAddr::from_str(value) {
+                Ok(ip) => {
+                    // a nonzero prefix length means the match is a network the
+                    // caller actually inserted, not the trie's implicit 0.0.0.0/0
+                    // (or ::/0) root that every lookup otherwise falls back to
+                    let matched = match ip {
+                        IpAddr::V4(ipv4) => {
+                            let net = ipv4_lctrie.lookup(&ipv4);
+                            (net.len() > 0).then(|| net.to_string())
+                        }
+                        IpAddr::V6(ipv6) => {
+                            let net = ipv6_lctrie.lookup(&ipv6);
+                            (net.len() > 0).then(|| net.to_string())
+                        }
+                    };
+                    match matched {
+                        Some(cidr) => builder.append_value(cidr),
+                        None => builder.append_null(),
+                    }
+                }
+                Err(_) => builder.append_null(), // Handle invalid IP strings
+            }
+        } else {
+            builder.append_null(); // Handle null input values
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
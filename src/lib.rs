@@ -1,3 +1,9 @@
+mod dns;
+mod dnsresolver;
+mod dynamic;
+mod dynamicdb;
+mod enrich;
+mod enrichdb;
 mod geoip;
 mod iptools;
 mod maxmind;
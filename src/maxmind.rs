@@ -1,59 +1,113 @@
 #![allow(clippy::unused_unit)]
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
 use lazy_static::lazy_static;
 use maxminddb::{geoip2, Mmap, Reader};
 use polars::prelude::*;
+use std::collections::BTreeMap;
 use std::env;
 use std::io;
 use std::net::IpAddr;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-
-// Define the fields and types that we will support
-pub const MAXMIND_FIELDS: [(&str, DataType); 12] = [
-    ("asnnum", DataType::UInt32),
-    ("asnorg", DataType::String),
-    ("city", DataType::String),
-    ("continent", DataType::String),
-    ("country", DataType::String),
-    ("country_iso", DataType::String),
-    ("latitude", DataType::Float64),
-    ("longitude", DataType::Float64),
-    ("postalcode", DataType::String),
-    ("subdivision", DataType::String),
-    ("subdivision_iso", DataType::String),
-    ("timezone", DataType::String),
-];
+use std::sync::{RwLock, RwLockReadGuard};
+use std::time::SystemTime;
+
+/// Resolve a localized name from a MaxMind `names` map: try the requested
+/// `languages` in order, then fall back to "en", then to any name present
+/// on the record, and finally to an empty string if the record has none.
+pub fn pick_name<'a>(names: &Option<BTreeMap<&'a str, &'a str>>, languages: &[String]) -> &'a str {
+    let Some(names) = names.as_ref() else {
+        return "";
+    };
+
+    for lang in languages {
+        if let Some(name) = names.get(lang.as_str()) {
+            return name;
+        }
+    }
+
+    if let Some(name) = names.get("en") {
+        return name;
+    }
+
+    names.values().next().copied().unwrap_or("")
+}
+
+// Define the fields and types that we will support. Since `subdivisions` is
+// a List of variable length, we cannot use const, but wrap in lazy_static so
+// we can still import it into other modules
+lazy_static! {
+    pub static ref MAXMIND_FIELDS: [(&'static str, DataType); 20] = [
+        ("accuracy_radius", DataType::UInt16),
+        ("asnnum", DataType::UInt32),
+        ("asnorg", DataType::String),
+        ("city", DataType::String),
+        ("continent", DataType::String),
+        ("country", DataType::String),
+        ("country_iso", DataType::String),
+        ("is_in_european_union", DataType::Boolean),
+        ("latitude", DataType::Float64),
+        ("longitude", DataType::Float64),
+        ("network", DataType::String),
+        ("postalcode", DataType::String),
+        ("registered_country", DataType::String),
+        ("registered_country_iso", DataType::String),
+        ("represented_country", DataType::String),
+        ("represented_country_iso", DataType::String),
+        ("subdivision", DataType::String),
+        ("subdivision_iso", DataType::String),
+        ("subdivisions", DataType::List(Box::new(DataType::String))),
+        ("timezone", DataType::String),
+    ];
+}
 
 // Define a struct to hold all the fields using &str instead of String
 pub struct MaxmindIPResult<'a> {
+    pub accuracy_radius: u16,
     pub asnnum: u32,
     pub asnorg: &'a str,
     pub city: &'a str,
     pub continent: &'a str,
     pub country: &'a str,
     pub country_iso: &'a str,
+    pub is_in_european_union: bool,
     pub latitude: f64,
     pub longitude: f64,
+    // CIDR of the network that matched in the City database, e.g. "8.8.8.0/24"
+    pub network: Option<String>,
     pub postalcode: &'a str,
+    pub registered_country: &'a str,
+    pub registered_country_iso: &'a str,
+    pub represented_country: &'a str,
+    pub represented_country_iso: &'a str,
     pub subdivision: &'a str,
     pub subdivision_iso: &'a str,
+    // ISO codes for every subdivision on the record, not just the first
+    pub subdivisions: Option<Vec<&'a str>>,
     pub timezone: &'a str,
 }
 
 impl<'a> Default for MaxmindIPResult<'a> {
     fn default() -> Self {
         Self {
+            accuracy_radius: 0,
             asnnum: 0,
             asnorg: "",
             city: "",
             continent: "",
             country: "",
             country_iso: "",
+            is_in_european_union: false,
             latitude: 0.0,
             longitude: 0.0,
+            network: None,
             postalcode: "",
+            registered_country: "",
+            registered_country_iso: "",
+            represented_country: "",
+            represented_country_iso: "",
             subdivision: "",
             subdivision_iso: "",
+            subdivisions: None,
             timezone: "",
         }
     }
@@ -61,17 +115,14 @@ impl<'a> Default for MaxmindIPResult<'a> {
 
 // Mutex implementation and error handling improvements provided
 // by ChatGPT on 20240717 using GPT-4o
-// This instantiates a lazily loaded global connection to MaxMind
-// mmdb database files for re-use
+// This instantiates lazily loaded global connections to MaxMind
+// mmdb database files for re-use. Each reader lives behind its own
+// RwLock so concurrent lookups (read lock) never block one another,
+// while a reload (write lock) swaps in freshly opened readers without
+// restarting the Python process.
 lazy_static! {
-    pub static ref MAXMIND_DB: Mutex<Option<Result<MaxMindDB, PolarsError>>> = Mutex::new(None);
-}
-
-/// Object to hold connections to ASN and City MaxMind MMDB readers
-#[derive(Debug)]
-pub struct MaxMindDB {
-    asn_reader: Reader<Mmap>,
-    city_reader: Reader<Mmap>,
+    static ref ASN_READER: RwLock<Option<LoadedReader>> = RwLock::new(None);
+    static ref CITY_READER: RwLock<Option<LoadedReader>> = RwLock::new(None);
 }
 
 /// Helper function to locate the MaxMind MMDB directory on the system
@@ -104,123 +155,206 @@ fn get_mmdb_dir() -> Result<PathBuf, io::Error> {
     ))
 }
 
-impl MaxMindDB {
-    /// Initialize the lookup readers by locating directory containing
-    /// MaxMind mmdb files and opening ASN and City readers. If directories are not
-    /// found or mmdb files could not be opened, raise a PolarsCompute
-    /// error so it propagates back up to the python user
-    fn initialize() -> PolarsResult<Self> {
-        let mmdb_dir_result = get_mmdb_dir();
-
-        if mmdb_dir_result.is_err() {
-            let error_message = "Error could not locate a directory for MaxMind MMDB files\n\
-                        Hint: specify a directory with the environment variable MAXMIND_MMDB_DIR\n";
-            return Err(PolarsError::ComputeError(error_message.into()));
-        }
+/// Resolve the path to a single MMDB file: an explicit path wins outright,
+/// otherwise fall back to the GEOIP_MMDB_DIR directory, and finally to the
+/// same MAXMIND_MMDB_DIR/default-path search `get_mmdb_dir` already performs.
+///
+/// Shared by every MaxMind-backed module (geoip, enrich) so they all resolve
+/// database directories the same way.
+pub(crate) fn resolve_mmdb_path(explicit: Option<&str>, filename: &str) -> PolarsResult<PathBuf> {
+    if let Some(path) = explicit {
+        return Ok(PathBuf::from(path));
+    }
 
-        let mmdb_dir = mmdb_dir_result.unwrap();
+    if let Ok(dir) = env::var("GEOIP_MMDB_DIR") {
+        return Ok(PathBuf::from(dir).join(filename));
+    }
 
-        let asn_path = Path::new(&mmdb_dir).join("GeoLite2-ASN.mmdb");
-        let city_path = Path::new(&mmdb_dir).join("GeoLite2-City.mmdb");
+    get_mmdb_dir().map(|dir| dir.join(filename)).map_err(|_| {
+        let error_message = "Error could not locate a directory for MaxMind MMDB files\n\
+            Hint: specify a directory with the environment variable GEOIP_MMDB_DIR \
+            (or MAXMIND_MMDB_DIR), or pass an explicit path to the mmdb file\n";
+        PolarsError::ComputeError(error_message.into())
+    })
+}
 
-        let asn_reader = Reader::open_mmap(&asn_path);
-        let city_reader = Reader::open_mmap(&city_path);
+pub(crate) fn open_reader(path: &Path, label: &str) -> PolarsResult<Reader<Mmap>> {
+    Reader::open_mmap(path).map_err(|_| {
+        let error_message = format!(
+            "Could not open {} MMDB file from {}",
+            label,
+            path.to_str().unwrap_or_default()
+        );
+        PolarsError::ComputeError(error_message.into())
+    })
+}
 
-        if asn_reader.is_err() {
-            let error_message = format!(
-                "Could not open ASN MMDB file from {}",
-                asn_path.to_str().unwrap_or_default()
-            );
-            return Err(PolarsError::ComputeError(error_message.into()));
+/// Build the network that matched a `lookup_prefix` call, given the looked-up
+/// `ip` and the prefix length that call returned.
+///
+/// GeoLite2/GeoIP2 City databases are built as a single `ip_version: 6` trie,
+/// with IPv4 networks stored at the 96-bit IPv4-mapped (`::ffff:0:0/96`)
+/// offset. Depending on the `maxminddb` version, `lookup_prefix` may return a
+/// prefix length already relative to the looked-up address's own family
+/// (<=32 for IPv4), or absolute within that 128-bit trie (96..=128 for
+/// IPv4). Normalize both so the emitted network always matches `ip`'s family.
+pub(crate) fn network_for_match(ip: IpAddr, prefix_len: usize) -> Option<IpNet> {
+    let net = match ip {
+        IpAddr::V4(ipv4) => {
+            // >32 can only mean the absolute 128-bit trie offset was
+            // returned, since an IPv4 prefix length is never more than 32.
+            let prefix_len = if prefix_len > 32 {
+                prefix_len.saturating_sub(96)
+            } else {
+                prefix_len
+            };
+            IpNet::V4(Ipv4Net::new(ipv4, prefix_len.min(32) as u8).ok()?)
         }
+        IpAddr::V6(ipv6) => IpNet::V6(Ipv6Net::new(ipv6, prefix_len.min(128) as u8).ok()?),
+    };
+    Some(net.trunc())
+}
 
-        if city_reader.is_err() {
-            let error_message = format!(
-                "Could not open City MMDB file from {}",
-                city_path.to_str().unwrap_or_default()
-            );
-            return Err(PolarsError::ComputeError(error_message.into()));
-        }
+/// An opened MMDB reader alongside the path and mtime it was opened with, so
+/// `get_or_init` can cheaply detect that the file on disk has changed.
+#[derive(Debug)]
+pub(crate) struct LoadedReader {
+    pub(crate) reader: Reader<Mmap>,
+    path: PathBuf,
+    mtime: Option<SystemTime>,
+}
 
-        Ok(Self {
-            asn_reader: asn_reader.unwrap(),
-            city_reader: city_reader.unwrap(),
-        })
-    }
+pub(crate) fn open_loaded_reader(path: PathBuf, label: &str) -> PolarsResult<LoadedReader> {
+    let reader = open_reader(&path, label)?;
+    let mtime = file_mtime(&path);
+    Ok(LoadedReader {
+        reader,
+        path,
+        mtime,
+    })
+}
+
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether a loaded reader's file has been modified on disk since it was
+/// opened. Used by `get_or_init` to transparently pick up MMDB updates
+/// without a process restart.
+pub(crate) fn is_stale(loaded: &LoadedReader) -> bool {
+    file_mtime(&loaded.path) != loaded.mtime
+}
+
+/// Opt out of the automatic hot-reload check with `IPTOOLS_MMDB_NO_AUTORELOAD`,
+/// for users who pin database files and want to avoid the per-invocation
+/// `stat` calls entirely.
+pub(crate) fn autoreload_enabled() -> bool {
+    env::var_os("IPTOOLS_MMDB_NO_AUTORELOAD").is_none()
+}
+
+/// Namespace for the global ASN and City MaxMind MMDB readers.
+pub struct MaxMindDB;
 
-    /// Force a reinitialization of the MMDB readers by dropping
-    /// the existing global reader and invoking initialize() again.
-    /// This is helpful, particularly in an interactive session (e.g., Jupyter)
-    /// and the user has changed MAXMIND_MMDB_DIR setting or updated
-    /// the MaxMind mmdb files themselves
-    pub fn reload() -> PolarsResult<()> {
-        let mut db = MAXMIND_DB.lock().unwrap();
-        *db = Some(Self::initialize());
+impl MaxMindDB {
+    /// (Re)open the ASN and City readers and atomically swap them into the
+    /// global slots under the write lock. This is helpful, particularly in
+    /// an interactive session (e.g., Jupyter), when the user has updated the
+    /// MaxMind mmdb files or wants to point at a different asn_path/city_path
+    /// without restarting the Python process.
+    pub fn reload(asn_path: Option<&str>, city_path: Option<&str>) -> PolarsResult<()> {
+        let asn_reader =
+            open_loaded_reader(resolve_mmdb_path(asn_path, "GeoLite2-ASN.mmdb")?, "ASN")?;
+        let city_reader =
+            open_loaded_reader(resolve_mmdb_path(city_path, "GeoLite2-City.mmdb")?, "City")?;
+
+        *ASN_READER.write().unwrap() = Some(asn_reader);
+        *CITY_READER.write().unwrap() = Some(city_reader);
         Ok(())
     }
 
-    /// Modeling OnceLock's get_or_init, gets the global mmdb reader,
-    /// initializing it first if necessary
+    /// Modeling OnceLock's get_or_init, returns a read-locked handle onto the
+    /// global readers, initializing them first if necessary. Once initialized,
+    /// and unless `IPTOOLS_MMDB_NO_AUTORELOAD` is set, cheaply stats both
+    /// files and transparently reopens them if either has changed on disk
+    /// since it was last loaded.
     pub fn get_or_init(
-    ) -> PolarsResult<std::sync::MutexGuard<'static, Option<Result<Self, PolarsError>>>> {
-        // Credit to GPT-4o for writing this method on 20240717
-        let mut db = MAXMIND_DB.lock().unwrap();
-        if db.is_none() {
-            *db = Some(Self::initialize());
+        asn_path: Option<&str>,
+        city_path: Option<&str>,
+    ) -> PolarsResult<MaxMindReaders> {
+        let needs_init =
+            ASN_READER.read().unwrap().is_none() || CITY_READER.read().unwrap().is_none();
+        if needs_init {
+            Self::reload(asn_path, city_path)?;
+        } else if autoreload_enabled() {
+            let stale = is_stale(ASN_READER.read().unwrap().as_ref().unwrap())
+                || is_stale(CITY_READER.read().unwrap().as_ref().unwrap());
+            if stale {
+                Self::reload(asn_path, city_path)?;
+            }
         }
-        Ok(db)
+
+        Ok(MaxMindReaders {
+            asn: ASN_READER.read().unwrap(),
+            city: CITY_READER.read().unwrap(),
+        })
     }
+}
+
+/// A read-locked handle onto the global ASN and City readers, held for the
+/// duration of a single expression invocation (i.e. one Series, not one row).
+pub struct MaxMindReaders {
+    asn: RwLockReadGuard<'static, Option<LoadedReader>>,
+    city: RwLockReadGuard<'static, Option<LoadedReader>>,
+}
 
+impl MaxMindReaders {
     pub fn asn_reader(&self) -> &Reader<Mmap> {
-        &self.asn_reader
+        &self
+            .asn
+            .as_ref()
+            .expect("ASN reader initialized by get_or_init")
+            .reader
     }
 
-    // pub fn city_reader(&self) -> &Reader<Mmap> {
-    //     &self.city_reader
-    // }
+    pub fn city_reader(&self) -> &Reader<Mmap> {
+        &self
+            .city
+            .as_ref()
+            .expect("City reader initialized by get_or_init")
+            .reader
+    }
 
-    pub fn iplookup(&self, ip: IpAddr) -> MaxmindIPResult<'_> {
+    pub fn iplookup(&self, ip: IpAddr, languages: &[String]) -> MaxmindIPResult<'_> {
         let mut result = MaxmindIPResult::default();
+        let asn_reader = self.asn_reader();
+        let city_reader = self.city_reader();
 
         // Lookup ASN information
-        if let Ok(asn) = self.asn_reader.lookup::<geoip2::Asn>(ip) {
+        if let Ok(asn) = asn_reader.lookup::<geoip2::Asn>(ip) {
             result.asnnum = asn.autonomous_system_number.unwrap_or(0);
             result.asnorg = asn.autonomous_system_organization.unwrap_or("");
         }
 
-        // Lookup City information
-        if let Ok(city_result) = self.city_reader.lookup::<geoip2::City>(ip) {
-            // as_ref() and &**s magic provided by ChatGPT on 20240825 using GPT-4o
+        // Lookup City information. lookup_prefix (rather than plain lookup) also
+        // hands back the prefix length of the network that matched, letting us
+        // surface the routed CIDR block alongside the usual record fields.
+        if let Ok((city_result, prefix_len)) = city_reader.lookup_prefix::<geoip2::City>(ip) {
             result.city = city_result
                 .city
                 .as_ref()
-                .and_then(|city| {
-                    city.names
-                        .as_ref()
-                        .and_then(|names| names.get("en").map(|s| &**s))
-                })
+                .map(|city| pick_name(&city.names, languages))
                 .unwrap_or("");
 
             result.continent = city_result
                 .continent
                 .as_ref()
-                .and_then(|continent| {
-                    continent
-                        .names
-                        .as_ref()
-                        .and_then(|names| names.get("en").map(|s| &**s))
-                })
+                .map(|continent| pick_name(&continent.names, languages))
                 .unwrap_or("");
 
             result.country = city_result
                 .country
                 .as_ref()
-                .and_then(|country| {
-                    country
-                        .names
-                        .as_ref()
-                        .and_then(|names| names.get("en").map(|s| &**s))
-                })
+                .map(|country| pick_name(&country.names, languages))
                 .unwrap_or("");
 
             result.country_iso = city_result
@@ -229,6 +363,12 @@ impl MaxMindDB {
                 .and_then(|country| country.iso_code)
                 .unwrap_or("");
 
+            result.is_in_european_union = city_result
+                .country
+                .as_ref()
+                .and_then(|country| country.is_in_european_union)
+                .unwrap_or(false);
+
             result.latitude = city_result
                 .location
                 .as_ref()
@@ -241,22 +381,42 @@ impl MaxMindDB {
                 .and_then(|loc| loc.longitude)
                 .unwrap_or(0.0);
 
+            result.network = network_for_match(ip, prefix_len).map(|net| net.to_string());
+
             result.postalcode = city_result
                 .postal
                 .as_ref()
                 .and_then(|postal| postal.code)
                 .unwrap_or("");
 
+            result.registered_country = city_result
+                .registered_country
+                .as_ref()
+                .map(|country| pick_name(&country.names, languages))
+                .unwrap_or("");
+
+            result.registered_country_iso = city_result
+                .registered_country
+                .as_ref()
+                .and_then(|country| country.iso_code)
+                .unwrap_or("");
+
+            result.represented_country = city_result
+                .represented_country
+                .as_ref()
+                .map(|country| pick_name(&country.names, languages))
+                .unwrap_or("");
+
+            result.represented_country_iso = city_result
+                .represented_country
+                .as_ref()
+                .and_then(|country| country.iso_code)
+                .unwrap_or("");
+
             result.subdivision = city_result
                 .subdivisions
                 .as_ref()
-                .and_then(|subs| {
-                    subs.first().and_then(|sub| {
-                        sub.names
-                            .as_ref()
-                            .and_then(|names| names.get("en").map(|s| &**s))
-                    })
-                })
+                .and_then(|subs| subs.first().map(|sub| pick_name(&sub.names, languages)))
                 .unwrap_or("");
 
             result.subdivision_iso = city_result
@@ -265,6 +425,18 @@ impl MaxMindDB {
                 .and_then(|subs| subs.first().and_then(|sub| sub.iso_code))
                 .unwrap_or("");
 
+            result.subdivisions = city_result.subdivisions.as_ref().map(|subs| {
+                subs.iter()
+                    .filter_map(|sub| sub.iso_code)
+                    .collect::<Vec<&str>>()
+            });
+
+            result.accuracy_radius = city_result
+                .location
+                .as_ref()
+                .and_then(|loc| loc.accuracy_radius)
+                .unwrap_or(0);
+
             result.timezone = city_result
                 .location
                 .as_ref()
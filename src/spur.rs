@@ -35,13 +35,7 @@ fn pl_full_spur(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
 
     let ca: &StringChunked = inputs[0].str()?;
 
-    // Create builders for all fields except 'services'
     let mut builders = create_builders(&SPUR_FIELDS, ca.len());
-    // Note: ListStringChunkedBuilder is created separately as adding it to the BuilderWrapper enum
-    // was too complicated for my rust skills. Each List is initialized with a capacity of 4, which is a
-    // generous estimate for the expected number of services per IP.
-    let mut services_builder =
-        ListStringChunkedBuilder::new(PlSmallStr::from("services"), ca.len(), 4);
 
     ca.into_iter().for_each(|op_s| {
         if let Some(ip_s) = op_s {
@@ -56,33 +50,27 @@ fn pl_full_spur(inputs: &[Series], kwargs: MMDBKwargs) -> PolarsResult<Series> {
                 builders[2].append_value(spuripresult.location_city);
                 builders[3].append_value(spuripresult.location_country);
                 builders[4].append_value(spuripresult.location_state);
-                //builders[5].append_value(spuripresult.services);
-                builders[5].append_value(spuripresult.tag);
-
-                // Add the services from the Option<Vec> into the standalone builder
-                if let Some(services) = &spuripresult.services {
-                    services_builder.append_values_iter(services.iter().copied());
-                } else {
-                    services_builder.append_null();
+                match &spuripresult.services {
+                    Some(services) => builders[5]
+                        .append_value(AnyValue::List(Series::new(PlSmallStr::EMPTY, services))),
+                    None => builders[5].append_null(),
                 }
+                builders[6].append_value(spuripresult.tag);
             } else {
                 // invalid ip, so append nulls for everything
                 builders
                     .iter_mut()
                     .for_each(|builder| builder.append_null());
-                services_builder.append_null();
             }
         } else {
             // null input, so append nulls for everything
             builders
                 .iter_mut()
                 .for_each(|builder| builder.append_null());
-            services_builder.append_null();
         }
     });
 
     // finalize builders and instantiate resulting Struct
-    let mut series: Vec<Series> = builders.into_iter().map(|b| b.finish()).collect();
-    series.push(services_builder.finish().into_series());
+    let series: Vec<Series> = builders.into_iter().map(|b| b.finish()).collect();
     StructChunked::from_series(PlSmallStr::from("spur"), &series).map(|ca| ca.into_series())
 }
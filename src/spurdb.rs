@@ -1,6 +1,5 @@
 #![allow(clippy::unused_unit)]
 use lazy_static::lazy_static;
-use maxminddb::{Mmap, Reader};
 use polars::prelude::*;
 use serde::Deserialize;
 // use std::borrow::Cow;
@@ -10,17 +9,19 @@ use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+use crate::maxmind::{autoreload_enabled, is_stale, open_loaded_reader, LoadedReader};
+
 // Define the fields and types that we will support. Since we have a List of variable
 // length, we cannot use const, but wrap in lazy_static so we can still import it
 // into other modules
 lazy_static! {
-    pub static ref SPUR_FIELDS: [(&'static str, DataType); 6] = [
+    pub static ref SPUR_FIELDS: [(&'static str, DataType); 7] = [
         ("client_count", DataType::Float32),
         ("infrastructure", DataType::String),
         ("location_city", DataType::String),
         ("location_country", DataType::String),
         ("location_state", DataType::String),
-        // ("services", DataType::List(Box::new(DataType::String))),
+        ("services", DataType::List(Box::new(DataType::String))),
         ("tag", DataType::String),
     ];
 }
@@ -77,7 +78,7 @@ lazy_static! {
 /// Object to hold connections to Spur maxmind MMDB readers
 #[derive(Debug)]
 pub struct SpurDB {
-    spur_reader: Reader<Mmap>,
+    spur_reader: LoadedReader,
 }
 
 /// Helper function to locate the Spur MMDB directory on the system
@@ -127,19 +128,9 @@ impl SpurDB {
         let mmdb_dir = mmdb_dir_result.unwrap();
 
         let spur_path = Path::new(&mmdb_dir).join("spur.mmdb");
-        let spur_reader = Reader::open_mmap(&spur_path);
-
-        if spur_reader.is_err() {
-            let error_message = format!(
-                "Could not open Spur MMDB file from {}",
-                spur_path.to_str().unwrap_or_default()
-            );
-            return Err(PolarsError::ComputeError(error_message.into()));
-        }
+        let spur_reader = open_loaded_reader(spur_path, "Spur")?;
 
-        Ok(Self {
-            spur_reader: spur_reader.unwrap(),
-        })
+        Ok(Self { spur_reader })
     }
 
     /// Force a reinitialization of the MMDB readers by dropping
@@ -154,13 +145,20 @@ impl SpurDB {
     }
 
     /// Modeling OnceLock's get_or_init, gets the global mmdb reader,
-    /// initializing it first if necessary
+    /// initializing it first if necessary. Once initialized, and unless
+    /// `IPTOOLS_MMDB_NO_AUTORELOAD` is set, cheaply stats the Spur file and
+    /// transparently reopens it if it has changed on disk since it was last
+    /// loaded.
     pub fn get_or_init(
     ) -> PolarsResult<std::sync::MutexGuard<'static, Option<Result<Self, PolarsError>>>> {
         // Credit to GPT-4o for writing this method on 20240717
         let mut db = SPUR_DB.lock().unwrap();
-        if db.is_none() {
-            *db = Some(Self::initialize());
+        match db.as_ref() {
+            None => *db = Some(Self::initialize()),
+            Some(Ok(spur_db)) if autoreload_enabled() && is_stale(&spur_db.spur_reader) => {
+                *db = Some(Self::initialize())
+            }
+            _ => {}
         }
         Ok(db)
     }
@@ -169,7 +167,7 @@ impl SpurDB {
         let mut result = SpurResult::default();
 
         // Lookup spur information
-        if let Ok(record) = self.spur_reader.lookup::<SpurLookupResult>(ip) {
+        if let Ok(record) = self.spur_reader.reader.lookup::<SpurLookupResult>(ip) {
             // Populate the SpurLookupResult fields
             result.client_count = record.clientCount.unwrap_or_default();
             result.infrastructure = record.infrastructure.unwrap_or_default();
@@ -1,5 +1,10 @@
 use polars::{chunked_array::builder::NullChunkedBuilder, prelude::*};
 use serde::Deserialize;
+use std::collections::BTreeMap;
+
+fn default_languages() -> Vec<String> {
+    vec!["en".to_string()]
+}
 
 /// Kwargs struct for Polars expression params
 #[derive(Deserialize)]
@@ -7,6 +12,93 @@ pub struct MMDBKwargs {
     // geoip expressions should first reload/reinitialize mmdb files
     // before querying
     pub reload_mmdb: bool,
+    // ordered list of preferred ISO language codes used to resolve
+    // city/continent/country/subdivision names, e.g. ["de", "en"].
+    // falls back to "en", then to any name present on the record, and
+    // finally an empty string if the record has no names at all.
+    #[serde(default = "default_languages")]
+    pub languages: Vec<String>,
+    // explicit path to the GeoLite2-ASN.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub asn_path: Option<String>,
+    // explicit path to the GeoLite2-City.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub city_path: Option<String>,
+}
+
+/// Kwargs struct for the MaxMind enrichment expressions (Anonymous-IP,
+/// Connection-Type, ISP, Domain), parallel to MMDBKwargs but carrying a
+/// path for each of the four enrichment databases instead of ASN/City.
+#[derive(Deserialize)]
+pub struct EnrichKwargs {
+    // enrich expressions should first reload/reinitialize mmdb files
+    // before querying
+    pub reload_mmdb: bool,
+    // explicit path to the GeoIP2-Anonymous-IP.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub anonymous_ip_path: Option<String>,
+    // explicit path to the GeoIP2-Connection-Type.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub connection_type_path: Option<String>,
+    // explicit path to the GeoIP2-ISP.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub isp_path: Option<String>,
+    // explicit path to the GeoIP2-Domain.mmdb file, overriding the
+    // GEOIP_MMDB_DIR/MAXMIND_MMDB_DIR-based directory lookup
+    #[serde(default)]
+    pub domain_path: Option<String>,
+}
+
+/// Kwargs struct for the generic `pl_mmdb_lookup` expression. `fields` maps
+/// each dotted field path (e.g. "traits.is_anonymous_proxy") to the Polars
+/// dtype its values should be read as ("string", "float", "uint", or
+/// "bool"). The output struct's schema has to be fully known before any
+/// row is looked up, so the dtype is declared by the caller rather than
+/// sniffed from the first non-null value.
+#[derive(Deserialize)]
+pub struct MmdbLookupKwargs {
+    // pl_mmdb_lookup should first evict any cached reader for `path`
+    // before querying
+    pub reload_mmdb: bool,
+    // path to an arbitrary MaxMind .mmdb file, e.g. GeoIP2-Enterprise.mmdb
+    pub path: String,
+    // dotted field path -> dtype name ("string", "float", "uint", "bool").
+    // a BTreeMap keeps the resulting struct's columns in a stable,
+    // alphabetical-by-path order
+    pub fields: BTreeMap<String, String>,
+}
+
+fn default_dns_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_dns_concurrency() -> usize {
+    32
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Kwargs struct for the `pl_reverse_dns`/`pl_forward_dns` expressions.
+#[derive(Deserialize)]
+pub struct DnsKwargs {
+    // per-lookup timeout, in milliseconds
+    #[serde(default = "default_dns_timeout_ms")]
+    pub timeout_ms: u64,
+    // maximum number of in-flight resolutions at once
+    #[serde(default = "default_dns_concurrency")]
+    pub concurrency: usize,
+    // skip resolving RFC1918/private-range addresses so bulk enrichment
+    // doesn't leak internal addresses to external resolvers.
+    // only applies to pl_reverse_dns, which looks up IP addresses directly
+    #[serde(default = "default_true")]
+    pub skip_private: bool,
 }
 
 /// BuilderWrapper is an enum that wraps different types of Polars ChunkedBuilders.
@@ -14,15 +106,15 @@ pub struct MMDBKwargs {
 /// different data types, simplifying the process of building Series with mixed types.
 /// This allows for creating a vec/array of disparate builder types, enabling
 /// flexible handling of multiple data types within a single collection.
-///
-/// Adding ListString variant was beyond my rust skills, but leaving its commented
-/// components for future reference (or more skill contributor)
 pub enum BuilderWrapper {
+    Boolean(BooleanChunkedBuilder),
+    UInt16(PrimitiveChunkedBuilder<UInt16Type>),
     UInt32(PrimitiveChunkedBuilder<UInt32Type>),
+    UInt64(PrimitiveChunkedBuilder<UInt64Type>),
     Float32(PrimitiveChunkedBuilder<Float32Type>),
     Float64(PrimitiveChunkedBuilder<Float64Type>),
     String(StringChunkedBuilder),
-    // ListString(ListStringChunkedBuilder),
+    ListString(ListStringChunkedBuilder),
     Invalid(NullChunkedBuilder),
 }
 
@@ -33,89 +125,126 @@ impl BuilderWrapper {
     {
         let any_value: AnyValue = value.into();
         match self {
+            BuilderWrapper::Boolean(b) => {
+                if let AnyValue::Boolean(v) = any_value {
+                    b.append_value(v)
+                } else {
+                    b.append_null()
+                }
+            }
+            BuilderWrapper::UInt16(b) => {
+                if let AnyValue::UInt16(v) = any_value {
+                    b.append_value(v)
+                } else {
+                    b.append_null()
+                }
+            }
             BuilderWrapper::UInt32(b) => {
                 if let AnyValue::UInt32(v) = any_value {
                     b.append_value(v)
                 } else {
                     b.append_null()
                 }
-            },
+            }
+            BuilderWrapper::UInt64(b) => {
+                if let AnyValue::UInt64(v) = any_value {
+                    b.append_value(v)
+                } else {
+                    b.append_null()
+                }
+            }
             BuilderWrapper::Float32(b) => {
                 if let AnyValue::Float32(v) = any_value {
                     b.append_value(v)
                 } else {
                     b.append_null()
                 }
-            },
+            }
             BuilderWrapper::Float64(b) => {
                 if let AnyValue::Float64(v) = any_value {
                     b.append_value(v)
                 } else {
                     b.append_null()
                 }
-            },
+            }
             BuilderWrapper::String(b) => {
                 if let AnyValue::String(v) = any_value {
                     b.append_value(v)
                 } else {
                     b.append_null()
                 }
-            },
+            }
+            BuilderWrapper::ListString(b) => {
+                if let AnyValue::List(v) = any_value {
+                    let string_iter = v.iter().filter_map(|av| match av {
+                        AnyValue::String(s) => Some(s),
+                        _ => None,
+                    });
+                    b.append_values_iter(string_iter);
+                } else {
+                    b.append_null()
+                }
+            }
             BuilderWrapper::Invalid(b) => b.append_null(),
-            // BuilderWrapper::ListString(b) => {
-            //     if let AnyValue::List(v) = any_value {
-            //         let string_iter = v.iter().filter_map(|av| match av {
-            //             AnyValue::String(s) => Some(s),
-            //             _ => None,
-            //         });
-            //         b.append_values_iter(string_iter);
-            //     } else {
-            //         b.append_null()
-            //     }
-            // }
-            // BuilderWrapper::ListString(b) => {
-            //     // Special handling for Vec<&str>
-            //     let string_slice = value.as_ref();
-            //     let string_iter = string_slice.iter().copied();
-            //     b.append_values_iter(string_iter);
-            // }
         }
     }
 
     pub fn append_null(&mut self) {
         match self {
+            BuilderWrapper::Boolean(b) => b.append_null(),
+            BuilderWrapper::UInt16(b) => b.append_null(),
             BuilderWrapper::UInt32(b) => b.append_null(),
+            BuilderWrapper::UInt64(b) => b.append_null(),
             BuilderWrapper::Float32(b) => b.append_null(),
             BuilderWrapper::Float64(b) => b.append_null(),
             BuilderWrapper::String(b) => b.append_null(),
-            // BuilderWrapper::ListString(b) => b.append_null(),
+            BuilderWrapper::ListString(b) => b.append_null(),
             BuilderWrapper::Invalid(b) => b.append_null(),
         }
     }
 
     pub fn finish(self) -> Series {
         match self {
+            BuilderWrapper::Boolean(b) => b.finish().into_series(),
+            BuilderWrapper::UInt16(b) => b.finish().into_series(),
             BuilderWrapper::UInt32(b) => b.finish().into_series(),
+            BuilderWrapper::UInt64(b) => b.finish().into_series(),
             BuilderWrapper::Float32(b) => b.finish().into_series(),
             BuilderWrapper::Float64(b) => b.finish().into_series(),
             BuilderWrapper::String(b) => b.finish().into_series(),
-            // BuilderWrapper::ListString(mut b) => b.finish().into_series(),
+            BuilderWrapper::ListString(mut b) => b.finish().into_series(),
             BuilderWrapper::Invalid(b) => b.finish().into_series(),
         }
     }
 }
 
-pub fn create_builders<'a, const N: usize>(
-    fields: &'a [(&'a str, DataType); N],
+// Takes a slice rather than a fixed-size array so both the crate's fixed
+// field tables (MAXMIND_FIELDS, SPUR_FIELDS, ...) and a runtime-built
+// Vec<(&str, DataType)> (e.g. pl_mmdb_lookup's user-supplied field list)
+// can share this one builder factory.
+pub fn create_builders<'a>(
+    fields: &'a [(&'a str, DataType)],
     capacity: usize,
 ) -> Vec<BuilderWrapper> {
     fields
         .iter()
         .map(|(name, dtype)| match dtype {
+            DataType::Boolean => BuilderWrapper::Boolean(BooleanChunkedBuilder::new(
+                PlSmallStr::from_str(name),
+                capacity,
+            )),
+            DataType::UInt16 => BuilderWrapper::UInt16(PrimitiveChunkedBuilder::<UInt16Type>::new(
+                PlSmallStr::from_str(name),
+                capacity,
+            )),
             DataType::UInt32 => BuilderWrapper::UInt32(PrimitiveChunkedBuilder::<UInt32Type>::new(
                 PlSmallStr::from_str(name),
                 capacity,
             )),
+            DataType::UInt64 => BuilderWrapper::UInt64(PrimitiveChunkedBuilder::<UInt64Type>::new(
+                PlSmallStr::from_str(name),
+                capacity,
+            )),
             DataType::Float32 => BuilderWrapper::Float32(
                 PrimitiveChunkedBuilder::<Float32Type>::new(PlSmallStr::from_str(name), capacity),
             ),
@@ -126,9 +255,13 @@ pub fn create_builders<'a, const N: usize>(
                 PlSmallStr::from_str(name),
                 capacity,
             )),
-            // DataType::List(inner_type) if matches!(**inner_type, DataType::String) => {
-            //     BuilderWrapper::ListString(ListStringChunkedBuilder::new(name, capacity, 4))
-            // }
+            DataType::List(inner_type) if matches!(**inner_type, DataType::String) => {
+                BuilderWrapper::ListString(ListStringChunkedBuilder::new(
+                    PlSmallStr::from_str(name),
+                    capacity,
+                    4,
+                ))
+            }
             _ => {
                 let error_name = format!("{}_error", name);
                 BuilderWrapper::Invalid(NullChunkedBuilder::new(